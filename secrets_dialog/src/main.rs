@@ -1,9 +1,15 @@
+use base64::Engine;
 use eframe::egui;
+use rand::distributions::Alphanumeric;
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+use regex::Regex;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::env;
 use std::io::{self, Read};
 use std::process;
+use std::time::{Duration, Instant};
 
 // No changes needed to the data structures. They are well-defined.
 #[derive(Deserialize, Debug, Clone)]
@@ -11,6 +17,35 @@ struct DialogTemplate {
     title: String,
     description: Option<String>,
     fields: Vec<DialogField>,
+    virtual_keyboard: Option<bool>,
+    virtual_keyboard_only: Option<bool>,
+    virtual_keyboard_randomize: Option<bool>,
+    theme: Option<ThemeSpec>,
+    timeout_seconds: Option<u64>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct ThemeSpec {
+    mode: Option<String>,
+    accent: Option<String>,
+    logo: Option<String>,
+    logo_margin: Option<f32>,
+}
+
+/// Parses a `"#RRGGBB"` or `"RRGGBB"` hex string into an opaque color.
+fn parse_hex_color(hex: &str) -> Option<egui::Color32> {
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    // Checking ASCII hex digits (not just byte length) before slicing matters:
+    // a multi-byte UTF-8 string can total 6 bytes without each slice boundary
+    // below landing on a char boundary, which would panic instead of just
+    // rejecting a malformed (attacker/caller-supplied) theme.accent value.
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some(egui::Color32::from_rgb(r, g, b))
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -22,6 +57,55 @@ struct DialogField {
     default: Option<String>,
     placeholder: Option<String>,
     help_text: Option<String>,
+    options: Option<Vec<String>>,
+    min: Option<f64>,
+    max: Option<f64>,
+    pattern: Option<String>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    confirm: Option<String>,
+    generate: Option<GenerateSpec>,
+    show_if: Option<ShowIf>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct ShowIf {
+    field: String,
+    equals: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct GenerateSpec {
+    length: usize,
+    charset: Option<String>,
+}
+
+impl GenerateSpec {
+    /// Produces a CSPRNG-backed secret matching this spec's length and charset.
+    fn random_value(&self) -> String {
+        let mut rng = thread_rng();
+
+        match self.charset.as_deref().unwrap_or("alphanumeric") {
+            "hex" => {
+                const HEX: &[u8] = b"0123456789abcdef";
+                (0..self.length)
+                    .map(|_| HEX[rng.gen_range(0..HEX.len())] as char)
+                    .collect()
+            }
+            "base64" => {
+                const BASE64: &[u8] =
+                    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+                (0..self.length)
+                    .map(|_| BASE64[rng.gen_range(0..BASE64.len())] as char)
+                    .collect()
+            }
+            _ => rng
+                .sample_iter(&Alphanumeric)
+                .take(self.length)
+                .map(char::from)
+                .collect(),
+        }
+    }
 }
 
 impl DialogField {
@@ -33,8 +117,39 @@ impl DialogField {
         self.field_type.as_deref() == Some("password")
     }
 
+    fn is_select(&self) -> bool {
+        self.field_type.as_deref() == Some("select")
+    }
+
+    fn is_checkbox(&self) -> bool {
+        self.field_type.as_deref() == Some("checkbox")
+    }
+
+    fn is_multiline(&self) -> bool {
+        self.field_type.as_deref() == Some("multiline")
+    }
+
+    fn is_number(&self) -> bool {
+        self.field_type.as_deref() == Some("number")
+    }
+
+    /// Whether this field's controlling field (if any) currently holds the
+    /// required value, re-evaluated every frame against live field values.
+    fn is_visible(&self, field_values: &HashMap<String, String>) -> bool {
+        match &self.show_if {
+            Some(condition) => field_values.get(&condition.field) == Some(&condition.equals),
+            None => true,
+        }
+    }
+
     fn get_default(&self) -> String {
-        self.default.clone().unwrap_or_default()
+        if let Some(default) = &self.default {
+            default.clone()
+        } else if self.is_checkbox() {
+            "false".to_string()
+        } else {
+            String::new()
+        }
     }
 }
 
@@ -42,10 +157,65 @@ impl DialogTemplate {
     fn from_json(json: &str) -> Result<Self, serde_json::Error> {
         serde_json::from_str(json)
     }
+
+    fn wants_virtual_keyboard(&self) -> bool {
+        self.virtual_keyboard.unwrap_or(false)
+    }
+
+    fn virtual_keyboard_only(&self) -> bool {
+        self.virtual_keyboard_only.unwrap_or(false)
+    }
+}
+
+/// A single key on the on-screen keyboard.
+#[derive(Clone)]
+enum VirtualKey {
+    Char(char),
+    Shift,
+    Backspace,
+    Space,
+}
+
+/// Builds the on-screen keyboard layout, optionally shuffling the character
+/// keys (letters, digits, symbols) within each row so a shoulder-surfer can't rely on fixed positions.
+fn build_keyboard_layout(randomize: bool) -> Vec<Vec<VirtualKey>> {
+    let mut rows: Vec<Vec<VirtualKey>> = vec![
+        "1234567890".chars().map(VirtualKey::Char).collect(),
+        "qwertyuiop".chars().map(VirtualKey::Char).collect(),
+        "asdfghjkl".chars().map(VirtualKey::Char).collect(),
+        "zxcvbnm".chars().map(VirtualKey::Char).collect(),
+        // Symbols, so virtual-only input can still satisfy a `pattern` that
+        // requires punctuation (a common secret policy).
+        "!@#$%^&*()-_=+[]{}".chars().map(VirtualKey::Char).collect(),
+    ];
+
+    if randomize {
+        let mut rng = thread_rng();
+        for row in rows.iter_mut() {
+            row.shuffle(&mut rng);
+        }
+    }
+
+    rows.last_mut().unwrap().push(VirtualKey::Backspace);
+    rows.push(vec![VirtualKey::Shift, VirtualKey::Space]);
+    rows
 }
 
-fn serialize_result(fields: &HashMap<String, String>) -> Result<String, serde_json::Error> {
-    serde_json::to_string(fields)
+/// Serializes the completed-prompt payload: `{"status":"completed","fields":{...}}`.
+fn serialize_completed(fields: &HashMap<String, String>) -> Result<String, serde_json::Error> {
+    serde_json::to_string(&serde_json::json!({
+        "status": "completed",
+        "fields": fields,
+    }))
+}
+
+/// Formats a clamped number for display, dropping the decimal point for whole values.
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        value.to_string()
+    }
 }
 
 struct DialogApp {
@@ -53,67 +223,313 @@ struct DialogApp {
     field_values: HashMap<String, String>,
     completed: bool,
     cancelled: bool,
-    error_message: Option<String>,
+    field_errors: HashMap<String, String>,
+    compiled_patterns: HashMap<String, Regex>,
+    keyboard_layout: Vec<Vec<VirtualKey>>,
+    keyboard_shift: bool,
+    focused_field: Option<String>,
+    pending_events: Vec<egui::Event>,
+    reveal_password: HashMap<String, bool>,
+    accent_color: Option<egui::Color32>,
+    logo_bytes: Option<Vec<u8>>,
+    theme_applied: bool,
+    start_time: Instant,
+    timed_out: bool,
 }
 
 impl DialogApp {
     fn new(template: DialogTemplate) -> Self {
         let mut field_values = HashMap::new();
-        
+        let mut compiled_patterns = HashMap::new();
+
         // Initialize with default values. This part is correct.
         for field in &template.fields {
             field_values.insert(field.name.clone(), field.get_default());
+
+            // Precompile patterns so a malformed template fails fast instead of
+            // surfacing a confusing error deep inside validation.
+            if let Some(pattern) = &field.pattern {
+                match Regex::new(pattern) {
+                    Ok(re) => {
+                        compiled_patterns.insert(field.name.clone(), re);
+                    }
+                    Err(e) => {
+                        eprintln!("Invalid pattern for field '{}': {}", field.name, e);
+                        process::exit(2);
+                    }
+                }
+            }
         }
-        
+
+        let keyboard_layout = build_keyboard_layout(template.virtual_keyboard_randomize.unwrap_or(false));
+
+        let accent_color = template
+            .theme
+            .as_ref()
+            .and_then(|theme| theme.accent.as_deref())
+            .and_then(parse_hex_color);
+
+        let logo_bytes = template
+            .theme
+            .as_ref()
+            .and_then(|theme| theme.logo.as_deref())
+            .and_then(|b64| base64::engine::general_purpose::STANDARD.decode(b64).ok());
+
         Self {
             template,
             field_values,
             completed: false,
             cancelled: false,
-            error_message: None,
+            field_errors: HashMap::new(),
+            compiled_patterns,
+            keyboard_layout,
+            keyboard_shift: false,
+            focused_field: None,
+            pending_events: Vec::new(),
+            reveal_password: HashMap::new(),
+            accent_color,
+            logo_bytes,
+            theme_applied: false,
+            start_time: Instant::now(),
+            timed_out: false,
         }
     }
 
-    /// Validates the form fields and updates the error message if needed.
+    /// Validates the form fields and updates the per-field error messages if needed.
     /// Returns true if validation passes.
     fn validate_and_submit(&mut self) -> bool {
-        let mut validation_errors = Vec::new();
-        
+        let mut field_errors = HashMap::new();
+        let mut clamped_numbers = Vec::new();
+
         for field in &self.template.fields {
-            if field.is_required() {
-                let value = self.field_values.get(&field.name).map_or("", |v| v.trim());
-                if value.is_empty() {
-                    validation_errors.push(format!("'{}' is required", field.label));
+            if !field.is_visible(&self.field_values) {
+                continue;
+            }
+
+            let value = self
+                .field_values
+                .get(&field.name)
+                .map_or(String::new(), |v| v.trim().to_string());
+
+            if field.is_required() && value.is_empty() {
+                field_errors.insert(field.name.clone(), format!("'{}' is required", field.label));
+                continue;
+            }
+
+            if value.is_empty() {
+                continue;
+            }
+
+            if field.is_select() {
+                if let Some(options) = &field.options {
+                    if !options.iter().any(|o| o == &value) {
+                        field_errors.insert(
+                            field.name.clone(),
+                            format!("'{}' must be one of the provided options", field.label),
+                        );
+                        continue;
+                    }
+                }
+            }
+
+            // The UI only reformats/clamps a number field on blur, which never
+            // fires if OK is clicked directly out of the text edit. Re-validate
+            // and re-clamp here so a malformed or out-of-range value can never
+            // actually be submitted.
+            if field.is_number() {
+                match value.parse::<f64>() {
+                    Ok(mut parsed) => {
+                        if let Some(min) = field.min {
+                            parsed = parsed.max(min);
+                        }
+                        if let Some(max) = field.max {
+                            parsed = parsed.min(max);
+                        }
+                        clamped_numbers.push((field.name.clone(), format_number(parsed)));
+                    }
+                    Err(_) => {
+                        field_errors.insert(
+                            field.name.clone(),
+                            format!("'{}' must be a valid number", field.label),
+                        );
+                        continue;
+                    }
+                }
+            }
+
+            if let Some(min_length) = field.min_length {
+                if value.chars().count() < min_length {
+                    field_errors.insert(
+                        field.name.clone(),
+                        format!("'{}' must be at least {} characters", field.label, min_length),
+                    );
+                    continue;
+                }
+            }
+
+            if let Some(max_length) = field.max_length {
+                if value.chars().count() > max_length {
+                    field_errors.insert(
+                        field.name.clone(),
+                        format!("'{}' must be at most {} characters", field.label, max_length),
+                    );
+                    continue;
+                }
+            }
+
+            if let Some(pattern) = self.compiled_patterns.get(&field.name) {
+                if !pattern.is_match(&value) {
+                    field_errors.insert(
+                        field.name.clone(),
+                        format!("'{}' does not match the required format", field.label),
+                    );
+                    continue;
+                }
+            }
+
+            if let Some(confirm_field) = &field.confirm {
+                // A confirm target hidden via show_if was never shown to the
+                // user to fill in, so comparing against it would make the form
+                // unsatisfiable; skip the check in that case.
+                let target_visible = self
+                    .template
+                    .fields
+                    .iter()
+                    .find(|f| &f.name == confirm_field)
+                    .is_none_or(|f| f.is_visible(&self.field_values));
+
+                if target_visible {
+                    let confirm_value = self
+                        .field_values
+                        .get(confirm_field)
+                        .map_or("", |v| v.trim());
+                    if value != confirm_value {
+                        field_errors.insert(
+                            field.name.clone(),
+                            format!("'{}' does not match '{}'", field.label, confirm_field),
+                        );
+                        continue;
+                    }
                 }
             }
         }
-        
-        if validation_errors.is_empty() {
+
+        if field_errors.is_empty() {
+            for (name, value) in clamped_numbers {
+                self.field_values.insert(name, value);
+            }
+            self.field_errors.clear();
             self.completed = true;
             true
         } else {
-            self.error_message = Some(validation_errors.join(", "));
+            self.field_errors = field_errors;
             false
         }
     }
 }
 
+impl DialogApp {
+    /// Translates a virtual keyboard button press into the egui event(s) it
+    /// stands for, queued up for delivery on the next frame's raw input.
+    fn handle_virtual_key(&mut self, key: &VirtualKey) {
+        match key {
+            VirtualKey::Char(c) => {
+                let ch = if self.keyboard_shift {
+                    c.to_ascii_uppercase()
+                } else {
+                    *c
+                };
+                self.pending_events.push(egui::Event::Text(ch.to_string()));
+                self.keyboard_shift = false;
+            }
+            VirtualKey::Shift => {
+                self.keyboard_shift = !self.keyboard_shift;
+            }
+            VirtualKey::Backspace => {
+                self.pending_events.push(egui::Event::Key {
+                    key: egui::Key::Backspace,
+                    physical_key: None,
+                    pressed: true,
+                    repeat: false,
+                    modifiers: egui::Modifiers::NONE,
+                });
+            }
+            VirtualKey::Space => {
+                self.pending_events.push(egui::Event::Text(" ".to_string()));
+            }
+        }
+    }
+}
+
 impl eframe::App for DialogApp {
+    /// Runs before `update` each frame. This is where synthesized virtual
+    /// keyboard events are injected into the real input stream so they reach
+    /// whichever `TextEdit` currently holds focus, and where text typed on the
+    /// real keyboard is dropped if the template asked for virtual-only input
+    /// (non-text key events such as Backspace still pass through).
+    fn raw_input_hook(&mut self, _ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        if self.template.virtual_keyboard_only() {
+            raw_input
+                .events
+                .retain(|event| !matches!(event, egui::Event::Text(_)));
+        }
+
+        raw_input.events.append(&mut self.pending_events);
+    }
+
     /// This is the core UI rendering loop.
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Apply the template's requested light/dark mode once. "system" (or no
+        // theme at all) is left alone so the dialog follows the OS default that
+        // eframe already picked up.
+        if !self.theme_applied {
+            match self.template.theme.as_ref().and_then(|t| t.mode.as_deref()) {
+                Some("dark") => ctx.set_visuals(egui::Visuals::dark()),
+                Some("light") => ctx.set_visuals(egui::Visuals::light()),
+                _ => {}
+            }
+            self.theme_applied = true;
+        }
+
+        // Auto-cancel an unattended prompt. Repainting on a timer (rather than
+        // only on input) is what keeps the countdown below ticking and lets
+        // this fire even if the user never touches the window.
+        if let Some(timeout_secs) = self.template.timeout_seconds {
+            let remaining = Duration::from_secs(timeout_secs).saturating_sub(self.start_time.elapsed());
+            if remaining.is_zero() {
+                self.timed_out = true;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            } else {
+                ctx.request_repaint_after(Duration::from_secs(1));
+            }
+        }
+
         // --- Buttons Panel (Bottom) ---
         // Use a TopBottomPanel to dock the buttons to the bottom of the window.
         // This ensures they are always visible and correctly placed, solving the
         // "extra space underneath" problem.
         egui::TopBottomPanel::bottom("buttons_panel").show(ctx, |ui| {
             ui.add_space(5.0); // Some padding above the buttons
+
+            if let Some(timeout_secs) = self.template.timeout_seconds {
+                let remaining =
+                    Duration::from_secs(timeout_secs).saturating_sub(self.start_time.elapsed());
+                ui.label(
+                    egui::RichText::new(format!("Auto-closing in {}s", remaining.as_secs()))
+                        .color(egui::Color32::GRAY),
+                );
+                ui.add_space(5.0);
+            }
+
             // Use a right-to-left layout to easily right-align the buttons.
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 // Add OK button first because of the right-to-left layout.
-                if ui.add_sized([80.0, 30.0], egui::Button::new("OK")).clicked() {
-                    if self.validate_and_submit() {
-                        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
-                    }
+                let mut ok_button = egui::Button::new("OK");
+                if let Some(accent) = self.accent_color {
+                    ok_button = ok_button.fill(accent);
+                }
+                if ui.add_sized([80.0, 30.0], ok_button).clicked() && self.validate_and_submit() {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                 }
                 
                 ui.add_space(10.0);
@@ -127,11 +543,66 @@ impl eframe::App for DialogApp {
             ui.add_space(5.0); // Some padding below the buttons
         });
 
+        // --- Virtual Keyboard Panel (Bottom, above the OK/Cancel buttons) ---
+        // Opt-in via the template, for entering secrets without touching the
+        // physical keyboard on a shared machine.
+        if self.template.wants_virtual_keyboard() {
+            egui::TopBottomPanel::bottom("virtual_keyboard_panel").show(ctx, |ui| {
+                ui.add_space(5.0);
+
+                if let Some(name) = &self.focused_field {
+                    if let Some(field) = self.template.fields.iter().find(|f| &f.name == name) {
+                        ui.label(format!("Typing into: {}", field.label));
+                    }
+                }
+
+                let layout = self.keyboard_layout.clone();
+                for row in &layout {
+                    // Wrapped, not plain horizontal: a row (e.g. the symbol row)
+                    // can be wider than the window, and this has no scrollbar.
+                    ui.horizontal_wrapped(|ui| {
+                        for key in row {
+                            let label = match key {
+                                VirtualKey::Char(c) => {
+                                    if self.keyboard_shift {
+                                        c.to_ascii_uppercase().to_string()
+                                    } else {
+                                        c.to_string()
+                                    }
+                                }
+                                VirtualKey::Shift => "Shift".to_string(),
+                                VirtualKey::Backspace => "<-".to_string(),
+                                VirtualKey::Space => "Space".to_string(),
+                            };
+                            if ui.add_sized([36.0, 28.0], egui::Button::new(label)).clicked() {
+                                self.handle_virtual_key(key);
+                            }
+                        }
+                    });
+                }
+
+                ui.add_space(5.0);
+            });
+        }
+
         // --- Main Content Panel (Central) ---
         // The CentralPanel will fill all remaining space between the top and bottom panels.
         egui::CentralPanel::default().show(ctx, |ui| {
+            // Optional branding logo, shown above everything else.
+            if let Some(bytes) = &self.logo_bytes {
+                let margin = self
+                    .template
+                    .theme
+                    .as_ref()
+                    .and_then(|t| t.logo_margin)
+                    .unwrap_or(8.0);
+                egui::Frame::none().inner_margin(margin).show(ui, |ui| {
+                    ui.add(egui::Image::from_bytes("bytes://logo.png", bytes.clone()));
+                });
+            }
+
             ui.add_space(10.0);
-            
+
             // Title
             ui.heading(&self.template.title);
             ui.add_space(5.0);
@@ -144,10 +615,11 @@ impl eframe::App for DialogApp {
             
             ui.separator();
 
-            // Error message
-            if let Some(error) = &self.error_message {
+            // Error banner. Field-specific detail is shown inline beneath each
+            // offending widget below, so this is just a heads-up.
+            if !self.field_errors.is_empty() {
                 ui.add_space(5.0);
-                ui.colored_label(egui::Color32::RED, error);
+                ui.colored_label(egui::Color32::RED, "Please correct the highlighted fields below.");
                 ui.add_space(5.0);
             }
             
@@ -164,35 +636,126 @@ impl eframe::App for DialogApp {
                     .min_col_width(100.0) // Minimum width for labels
                     .show(ui, |ui| {
                         for field in &self.template.fields {
-                            // Label with required indicator
-                            let mut label_text = field.label.clone();
-                            if field.is_required() {
-                                label_text.push_str(" *");
+                            // Re-evaluated every frame so a select/checkbox change shows or
+                            // hides dependent fields live.
+                            if !field.is_visible(&self.field_values) {
+                                continue;
                             }
-                            ui.label(label_text);
-                            
+
+                            // Label, shown in red when this field failed validation, with
+                            // a required-field asterisk in the template's accent color.
+                            ui.horizontal(|ui| {
+                                ui.spacing_mut().item_spacing.x = 2.0;
+                                if self.field_errors.contains_key(&field.name) {
+                                    ui.colored_label(egui::Color32::RED, &field.label);
+                                } else {
+                                    ui.label(&field.label);
+                                }
+                                if field.is_required() {
+                                    let asterisk_color =
+                                        self.accent_color.unwrap_or(egui::Color32::RED);
+                                    ui.colored_label(asterisk_color, "*");
+                                }
+                            });
+
                             // Get a mutable reference to the field's value.
                             let value = self.field_values.get_mut(&field.name).unwrap();
-                            
-                            // Create the TextEdit widget.
-                            let mut text_edit = egui::TextEdit::singleline(value)
-                                .hint_text(field.placeholder.as_deref().unwrap_or(""));
-                            
-                            if field.is_password() {
-                                text_edit = text_edit.password(true);
+
+                            // Render the widget appropriate for this field's type, all of
+                            // which still funnel their result back into `value` as a String
+                            // so `serialize_completed` doesn't need to know about field types.
+                            let response = if field.is_checkbox() {
+                                let mut checked = value == "true";
+                                let response = ui.checkbox(&mut checked, "");
+                                *value = checked.to_string();
+                                response
+                            } else if field.is_select() {
+                                let options = field.options.as_deref().unwrap_or(&[]);
+                                let selected_text = if value.is_empty() {
+                                    field.placeholder.as_deref().unwrap_or("Select...")
+                                } else {
+                                    value.as_str()
+                                };
+                                egui::ComboBox::from_id_source(&field.name)
+                                    .selected_text(selected_text)
+                                    .show_ui(ui, |ui| {
+                                        for option in options {
+                                            ui.selectable_value(value, option.clone(), option);
+                                        }
+                                    })
+                                    .response
+                            } else if field.is_multiline() {
+                                let text_edit = egui::TextEdit::multiline(value)
+                                    .hint_text(field.placeholder.as_deref().unwrap_or(""));
+                                ui.add(text_edit.desired_width(f32::INFINITY))
+                            } else if field.is_password() {
+                                let revealed = *self
+                                    .reveal_password
+                                    .get(&field.name)
+                                    .unwrap_or(&false);
+
+                                ui.horizontal(|ui| {
+                                    let text_edit = egui::TextEdit::singleline(value)
+                                        .hint_text(field.placeholder.as_deref().unwrap_or(""))
+                                        .password(!revealed);
+                                    let response =
+                                        ui.add(text_edit.desired_width(ui.available_width() - 140.0));
+
+                                    if ui.button(if revealed { "Hide" } else { "Show" }).clicked() {
+                                        self.reveal_password.insert(field.name.clone(), !revealed);
+                                    }
+
+                                    if let Some(generate) = &field.generate {
+                                        if ui.button("Generate").clicked() {
+                                            *value = generate.random_value();
+                                        }
+                                    }
+
+                                    response
+                                })
+                                .inner
+                            } else {
+                                let text_edit = egui::TextEdit::singleline(value)
+                                    .hint_text(field.placeholder.as_deref().unwrap_or(""));
+
+                                let response = ui.add(text_edit.desired_width(f32::INFINITY));
+
+                                if field.is_number() {
+                                    value.retain(|c| c.is_ascii_digit() || c == '-' || c == '.');
+                                    if response.lost_focus() {
+                                        if let Ok(mut parsed) = value.parse::<f64>() {
+                                            if let Some(min) = field.min {
+                                                parsed = parsed.max(min);
+                                            }
+                                            if let Some(max) = field.max {
+                                                parsed = parsed.min(max);
+                                            }
+                                            *value = format_number(parsed);
+                                        }
+                                    }
+                                }
+
+                                response
+                            };
+
+                            if response.has_focus() {
+                                self.focused_field = Some(field.name.clone());
                             }
-                            
-                            // Add the widget to the UI and get its response.
-                            // We use `fill_width` to make the input box expand.
-                            let response = ui.add(text_edit.desired_width(f32::INFINITY));
 
                             // Use hover text for help text. It's cleaner than adding more
                             // text directly to the layout.
                             if let Some(help) = &field.help_text {
                                 response.on_hover_text(help);
                             }
-                            
+
                             ui.end_row();
+
+                            // Inline error for this specific field, directly beneath its widget.
+                            if let Some(error) = self.field_errors.get(&field.name) {
+                                ui.label("");
+                                ui.colored_label(egui::Color32::RED, error);
+                                ui.end_row();
+                            }
                         }
                     });
             });
@@ -202,7 +765,21 @@ impl eframe::App for DialogApp {
     /// This function is called when the application is about to close.
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
         if self.completed {
-            match serialize_result(&self.field_values) {
+            // Hidden fields never got validated or seen by the user, so they're
+            // excluded from the emitted result rather than leaking stale values.
+            let visible_fields: HashMap<String, String> = self
+                .template
+                .fields
+                .iter()
+                .filter(|field| field.is_visible(&self.field_values))
+                .filter_map(|field| {
+                    self.field_values
+                        .get(&field.name)
+                        .map(|v| (field.name.clone(), v.clone()))
+                })
+                .collect();
+
+            match serialize_completed(&visible_fields) {
                 Ok(json) => {
                     println!("{}", json);
                     process::exit(0);
@@ -212,8 +789,12 @@ impl eframe::App for DialogApp {
                     process::exit(2);
                 }
             }
+        } else if self.timed_out {
+            println!("{}", serde_json::json!({ "status": "timed_out" }));
+            process::exit(3);
         } else {
             // User cancelled or closed the window.
+            println!("{}", serde_json::json!({ "status": "cancelled" }));
             process::exit(1);
         }
     }
@@ -258,6 +839,11 @@ fn main() -> Result<(), eframe::Error> {
     eframe::run_native(
         &title,
         options,
-        Box::new(|_cc| Ok(Box::new(DialogApp::new(template)))),
+        Box::new(|cc| {
+            // Required for `egui::Image::from_bytes` (used to render the
+            // template's branding logo) to have a loader to resolve against.
+            egui_extras::install_image_loaders(&cc.egui_ctx);
+            Ok(Box::new(DialogApp::new(template)))
+        }),
     )
 }